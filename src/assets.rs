@@ -0,0 +1,291 @@
+//! Functions and types for loading assets in the background, off of the main thread.
+//!
+//! GPU resources can only be created on the thread that owns the [`GLDevice`](crate::graphics::opengl::GLDevice),
+//! so the loader splits work into two phases: a fixed pool of worker threads read files from disk
+//! and decode them into raw, CPU-side buffers (pixels, PCM samples), and then [`poll`] uploads
+//! whatever has finished decoding to the GPU via `ctx.gl`, on whatever thread it is called from
+//! (normally the main thread, from within [`State::update`](crate::State::update)).
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::audio::Sound;
+use crate::graphics::{Font, Texture};
+use crate::{Context, Result, TetraError};
+
+/// The number of worker threads that are spawned to decode assets in the background.
+const WORKER_COUNT: usize = 4;
+
+/// A handle to an asset that is being loaded in the background.
+///
+/// The value will not be available until the loader that created this handle has been
+/// [polled](poll) enough times for the underlying file to finish loading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AssetHandle(usize);
+
+enum Kind {
+    Texture,
+    Font,
+    Sound,
+}
+
+struct Job {
+    handle: AssetHandle,
+    path: PathBuf,
+    kind: Kind,
+}
+
+enum RawAsset {
+    Texture { width: i32, height: i32, pixels: Vec<u8> },
+    Font(Vec<u8>),
+    Sound { samples: Vec<i16>, channels: u16, sample_rate: u32 },
+}
+
+enum LoadedAsset {
+    Texture(Texture),
+    Font(Font),
+    Sound(Sound),
+}
+
+struct PendingAsset {
+    handle: AssetHandle,
+    result: std::result::Result<RawAsset, TetraError>,
+}
+
+/// Loads a batch of assets on a fixed pool of background threads, uploading them to the GPU as
+/// they become ready.
+///
+/// This is useful for displaying a loading screen while the bulk of a game's textures, fonts and
+/// audio are being read from disk and decoded, rather than blocking the main thread (and therefore
+/// freezing the window) until everything is ready.
+pub struct AssetLoader {
+    job_sender: Sender<Job>,
+    result_receiver: Receiver<PendingAsset>,
+
+    loaded: Vec<Option<LoadedAsset>>,
+    total: usize,
+    done: usize,
+}
+
+impl AssetLoader {
+    /// Creates a new, empty `AssetLoader`, spawning its pool of worker threads.
+    pub fn new() -> AssetLoader {
+        let (job_sender, job_receiver) = mpsc::channel::<Job>();
+        let (result_sender, result_receiver) = mpsc::channel();
+
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+
+        for _ in 0..WORKER_COUNT {
+            let job_receiver = Arc::clone(&job_receiver);
+            let result_sender = result_sender.clone();
+
+            thread::spawn(move || loop {
+                // The lock is only held long enough to pull the next job off of the queue, so
+                // the other workers aren't blocked while this one reads/decodes its file.
+                let job = job_receiver.lock().unwrap().recv();
+
+                let job = match job {
+                    Ok(job) => job,
+                    Err(_) => break, // The loader (and its job_sender) was dropped.
+                };
+
+                let result = read_raw_asset(&job.path, job.kind);
+
+                // If the main thread has stopped polling (e.g. the loader was dropped), there's
+                // nothing to do with the result, so a failed send is ignored.
+                let _ = result_sender.send(PendingAsset {
+                    handle: job.handle,
+                    result,
+                });
+            });
+        }
+
+        AssetLoader {
+            job_sender,
+            result_receiver,
+
+            loaded: Vec::new(),
+            total: 0,
+            done: 0,
+        }
+    }
+
+    /// Queues a texture to be loaded from the given path, returning a handle that can later
+    /// be used to retrieve it via [`get_texture`](AssetLoader::get_texture).
+    pub fn load_texture<P>(&mut self, path: P) -> AssetHandle
+    where
+        P: Into<PathBuf>,
+    {
+        self.queue(path.into(), Kind::Texture)
+    }
+
+    /// Queues a font to be loaded from the given path, returning a handle that can later
+    /// be used to retrieve it via [`get_font`](AssetLoader::get_font).
+    pub fn load_font<P>(&mut self, path: P) -> AssetHandle
+    where
+        P: Into<PathBuf>,
+    {
+        self.queue(path.into(), Kind::Font)
+    }
+
+    /// Queues a sound to be loaded from the given path, returning a handle that can later
+    /// be used to retrieve it via [`get_sound`](AssetLoader::get_sound).
+    pub fn load_sound<P>(&mut self, path: P) -> AssetHandle
+    where
+        P: Into<PathBuf>,
+    {
+        self.queue(path.into(), Kind::Sound)
+    }
+
+    fn queue(&mut self, path: PathBuf, kind: Kind) -> AssetHandle {
+        let handle = AssetHandle(self.loaded.len());
+        self.loaded.push(None);
+        self.total += 1;
+
+        let _ = self.job_sender.send(Job { handle, path, kind });
+
+        handle
+    }
+
+    /// Retrieves a texture that has finished loading, if it is ready yet.
+    ///
+    /// The texture is moved out of the loader, so later calls with the same handle return `None`.
+    pub fn get_texture(&mut self, handle: AssetHandle) -> Option<Texture> {
+        match self.loaded.get_mut(handle.0)?.take()? {
+            LoadedAsset::Texture(texture) => Some(texture),
+            other => {
+                self.loaded[handle.0] = Some(other);
+                None
+            }
+        }
+    }
+
+    /// Retrieves a font that has finished loading, if it is ready yet.
+    ///
+    /// The font is moved out of the loader, so later calls with the same handle return `None`.
+    pub fn get_font(&mut self, handle: AssetHandle) -> Option<Font> {
+        match self.loaded.get_mut(handle.0)?.take()? {
+            LoadedAsset::Font(font) => Some(font),
+            other => {
+                self.loaded[handle.0] = Some(other);
+                None
+            }
+        }
+    }
+
+    /// Retrieves a sound that has finished loading, if it is ready yet.
+    ///
+    /// The sound is moved out of the loader, so later calls with the same handle return `None`.
+    pub fn get_sound(&mut self, handle: AssetHandle) -> Option<Sound> {
+        match self.loaded.get_mut(handle.0)?.take()? {
+            LoadedAsset::Sound(sound) => Some(sound),
+            other => {
+                self.loaded[handle.0] = Some(other);
+                None
+            }
+        }
+    }
+
+    /// Returns a number between `0.0` and `1.0`, representing how much of the queued work has
+    /// completed.
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.done as f32 / self.total as f32
+        }
+    }
+
+    /// Returns whether every queued asset has finished loading.
+    pub fn is_done(&self) -> bool {
+        self.done == self.total
+    }
+}
+
+impl Default for AssetLoader {
+    fn default() -> AssetLoader {
+        AssetLoader::new()
+    }
+}
+
+fn read_raw_asset(path: &Path, kind: Kind) -> std::result::Result<RawAsset, TetraError> {
+    let data = std::fs::read(path).map_err(|_| TetraError::FailedToLoadAsset)?;
+
+    Ok(match kind {
+        Kind::Texture => {
+            let (width, height, pixels) = decode_texture(&data)?;
+            RawAsset::Texture {
+                width,
+                height,
+                pixels,
+            }
+        }
+        // Font data isn't decoded into pixels up front - glyphs are rasterized on demand as
+        // they're used, so the only work here is the (already-done) file read.
+        Kind::Font => RawAsset::Font(data),
+        Kind::Sound => {
+            let (samples, channels, sample_rate) = decode_sound(&data)?;
+            RawAsset::Sound {
+                samples,
+                channels,
+                sample_rate,
+            }
+        }
+    })
+}
+
+/// Decodes an encoded image (PNG, JPEG, etc.) into raw RGBA8 pixels. This is the expensive part
+/// of texture loading, so it happens here, on the calling worker thread.
+fn decode_texture(data: &[u8]) -> std::result::Result<(i32, i32, Vec<u8>), TetraError> {
+    let image = image::load_from_memory(data)
+        .map_err(|_| TetraError::FailedToLoadAsset)?
+        .to_rgba8();
+
+    let (width, height) = image.dimensions();
+
+    Ok((width as i32, height as i32, image.into_raw()))
+}
+
+/// Decodes an encoded audio file into raw PCM samples. This is the expensive part of sound
+/// loading, so it happens here, on the calling worker thread.
+fn decode_sound(data: &[u8]) -> std::result::Result<(Vec<i16>, u16, u32), TetraError> {
+    let decoder = rodio::Decoder::new(std::io::Cursor::new(data.to_vec()))
+        .map_err(|_| TetraError::FailedToLoadAsset)?;
+
+    let channels = decoder.channels();
+    let sample_rate = decoder.sample_rate();
+    let samples = decoder.collect();
+
+    Ok((samples, channels, sample_rate))
+}
+
+/// Drains any assets that have finished decoding on a background thread, uploading them to the
+/// GPU via the current [`Context`].
+///
+/// This should be called once per frame, usually from [`State::update`](crate::State::update),
+/// for as long as the loader you're polling is not yet [`is_done`](AssetLoader::is_done).
+pub fn poll(ctx: &mut Context, loader: &mut AssetLoader) -> Result {
+    while let Ok(pending) = loader.result_receiver.try_recv() {
+        let loaded = match pending.result {
+            Ok(RawAsset::Texture {
+                width,
+                height,
+                pixels,
+            }) => LoadedAsset::Texture(Texture::from_rgba(ctx, width, height, &pixels)?),
+            Ok(RawAsset::Font(data)) => LoadedAsset::Font(Font::from_data(ctx, data)?),
+            Ok(RawAsset::Sound {
+                samples,
+                channels,
+                sample_rate,
+            }) => LoadedAsset::Sound(Sound::from_samples(samples, channels, sample_rate)?),
+            Err(e) => return Err(e),
+        };
+
+        loader.loaded[pending.handle.0] = Some(loaded);
+        loader.done += 1;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Functions and types relating to the events fired by the windowing system.
+
+use std::path::PathBuf;
+
+use crate::input::Key;
+
+/// Events that can occur while the game is running, and can be handled via
+/// [`State::event`](crate::State::event).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Event {
+    /// The window was resized.
+    Resized {
+        /// The new width of the window.
+        width: i32,
+        /// The new height of the window.
+        height: i32,
+    },
+
+    /// The window gained focus.
+    FocusGained,
+
+    /// The window lost focus.
+    FocusLost,
+
+    /// A key on the keyboard was pressed.
+    KeyPressed(Key),
+
+    /// A key on the keyboard was released.
+    KeyReleased(Key),
+
+    /// A Unicode character was entered into the window (e.g. via a text input field).
+    TextInput(String),
+
+    /// A file was dropped onto the window.
+    FileDropped(PathBuf),
+}
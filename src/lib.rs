@@ -59,17 +59,21 @@
 
 #![warn(missing_docs)]
 
+pub mod assets;
 pub mod audio;
 pub mod error;
+pub mod event;
 mod fs;
 pub mod glm;
 pub mod graphics;
 pub mod input;
 mod platform;
+pub mod scene;
 pub mod time;
 pub mod window;
 
 pub use crate::error::{Result, TetraError};
+pub use crate::event::Event;
 use crate::graphics::opengl::GLDevice;
 use crate::graphics::GraphicsContext;
 use crate::input::InputContext;
@@ -108,6 +112,16 @@ pub trait State {
         Ok(())
     }
 
+    /// Called when a window or input event occurs.
+    ///
+    /// This is delivered separately from `update`, as events can happen at any point in the
+    /// frame (and can happen more than once per frame) - this makes it suitable for things that
+    /// need a direct response, like reacting to the window being resized, handling files
+    /// dropped onto the window or text input, rather than being polled for every tick.
+    fn event(&mut self, ctx: &mut Context, event: Event) -> Result {
+        Ok(())
+    }
+
     fn error(error: TetraError) {
         println!("Error: {}", error);
     }
@@ -124,6 +138,7 @@ pub struct Context {
 
     running: bool,
     quit_on_escape: bool,
+    timing_mode: TimingMode,
 }
 
 impl Context {
@@ -145,17 +160,53 @@ impl Context {
 
             running: false,
             quit_on_escape: builder.quit_on_escape,
+            timing_mode: builder.timing_mode,
         })
     }
 }
 
+/// Controls how the game synchronizes its drawing with the display's refresh rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VSyncMode {
+    /// Do not wait for the display to refresh before presenting a frame. This can result in
+    /// screen tearing, but allows the game to run as fast as the hardware will allow.
+    Off,
+
+    /// Wait for the display to refresh before presenting a frame.
+    On,
+
+    /// Behaves like [`On`](VSyncMode::On), except that if a frame is presented late, the swap
+    /// will happen immediately rather than waiting for the next refresh (reducing stutter at
+    /// the cost of potential tearing on that frame only).
+    ///
+    /// This maps to SDL's 'late swap tearing' swap interval. Not every driver supports it - if
+    /// it's rejected, Tetra will fall back to [`On`](VSyncMode::On).
+    Adaptive,
+}
+
+/// Controls how often [`State::update`](crate::State::update) is called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingMode {
+    /// Run updates at a fixed rate (set via [`ContextBuilder::tick_rate`]), independent of the
+    /// framerate, catching up or waiting as required. This is the default, and gives
+    /// deterministic game logic - see [Fix Your Timestep](https://gafferongames.com/post/fix_your_timestep/).
+    Fixed,
+
+    /// Run exactly one update per frame, with the real elapsed time available via
+    /// [`time::get_delta_time`]. This is simpler to reason about on displays where the
+    /// framerate is already locked (e.g. via vsync), at the cost of updates no longer being
+    /// deterministic.
+    Variable,
+}
+
 /// Creates a new `Context` based on the provided options.
 #[derive(Debug, Clone)]
 pub struct ContextBuilder {
     title: String,
     window_width: i32,
     window_height: i32,
-    vsync: bool,
+    vsync_mode: VSyncMode,
+    timing_mode: TimingMode,
     tick_rate: f64,
     fullscreen: bool,
     maximized: bool,
@@ -192,11 +243,19 @@ impl ContextBuilder {
         self
     }
 
-    /// Enables or disables vsync.
+    /// Sets the vsync mode.
     ///
-    /// Defaults to `true`.
-    pub fn vsync(&mut self, vsync: bool) -> &mut ContextBuilder {
-        self.vsync = vsync;
+    /// Defaults to [`VSyncMode::On`].
+    pub fn vsync_mode(&mut self, vsync_mode: VSyncMode) -> &mut ContextBuilder {
+        self.vsync_mode = vsync_mode;
+        self
+    }
+
+    /// Sets how [`State::update`](State::update) is paced.
+    ///
+    /// Defaults to [`TimingMode::Fixed`].
+    pub fn timing_mode(&mut self, timing_mode: TimingMode) -> &mut ContextBuilder {
+        self.timing_mode = timing_mode;
         self
     }
 
@@ -292,7 +351,8 @@ impl Default for ContextBuilder {
             title: "Tetra".into(),
             window_width: 1280,
             window_height: 720,
-            vsync: true,
+            vsync_mode: VSyncMode::On,
+            timing_mode: TimingMode::Fixed,
             tick_rate: 1.0 / 60.0,
             fullscreen: false,
             maximized: false,
@@ -311,23 +371,44 @@ where
 {
     time::tick(ctx);
 
-    if let Err(e) = platform::handle_events(ctx) {
+    if let Err(e) = platform::handle_events(ctx, state) {
         ctx.running = false;
         return S::error(e);
     }
 
-    while time::is_tick_ready(ctx) {
-        if let Err(e) = state.update(ctx) {
-            ctx.running = false;
-            return S::error(e);
+    let alpha = match ctx.timing_mode {
+        TimingMode::Fixed => {
+            while time::is_tick_ready(ctx) {
+                if let Err(e) = state.update(ctx) {
+                    ctx.running = false;
+                    return S::error(e);
+                }
+
+                input::cleanup_after_state_update(ctx);
+
+                time::consume_tick(ctx);
+            }
+
+            time::get_alpha(ctx)
         }
+        TimingMode::Variable => {
+            if let Err(e) = state.update(ctx) {
+                ctx.running = false;
+                return S::error(e);
+            }
 
-        input::cleanup_after_state_update(ctx);
+            input::cleanup_after_state_update(ctx);
 
-        time::consume_tick(ctx);
-    }
+            // The tick accumulator isn't used in this mode - each frame gets exactly one
+            // update, so it's cleared rather than drained, and the draw that follows is
+            // never interpolated.
+            time::consume_all(ctx);
+
+            1.0
+        }
+    };
 
-    if let Err(e) = state.draw(ctx, time::get_alpha(ctx)) {
+    if let Err(e) = state.draw(ctx, alpha) {
         ctx.running = false;
         return S::error(e);
     }
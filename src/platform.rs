@@ -0,0 +1,172 @@
+// Glue code between Tetra and the windowing/event backend (currently SDL2).
+
+use sdl2::event::{Event as SdlEvent, WindowEvent};
+use sdl2::video::{GLContext, SwapInterval, Window};
+use sdl2::{EventPump, Sdl, VideoSubsystem};
+
+use crate::error::Result;
+use crate::event::Event;
+use crate::graphics;
+use crate::input;
+use crate::{Context, ContextBuilder, State, VSyncMode};
+
+pub(crate) struct Platform {
+    sdl: Sdl,
+    _video: VideoSubsystem,
+    window: Window,
+    event_pump: EventPump,
+}
+
+impl Platform {
+    pub(crate) fn new(builder: &ContextBuilder) -> Result<(Platform, GLContext, i32, i32)> {
+        let sdl = sdl2::init().map_err(crate::TetraError::PlatformError)?;
+        let video = sdl.video().map_err(crate::TetraError::PlatformError)?;
+
+        let mut window_builder = video.window(&builder.title, builder.window_width as u32, builder.window_height as u32);
+
+        window_builder.opengl().position_centered();
+
+        if builder.fullscreen {
+            window_builder.fullscreen();
+        }
+
+        if builder.maximized {
+            window_builder.maximized();
+        }
+
+        if builder.minimized {
+            window_builder.minimized();
+        }
+
+        if builder.resizable {
+            window_builder.resizable();
+        }
+
+        if builder.borderless {
+            window_builder.borderless();
+        }
+
+        let window = window_builder
+            .build()
+            .map_err(|e| crate::TetraError::PlatformError(e.to_string()))?;
+
+        let gl_context = window
+            .gl_create_context()
+            .map_err(crate::TetraError::PlatformError)?;
+
+        let swap_interval = match builder.vsync_mode {
+            VSyncMode::Off => SwapInterval::Immediate,
+            VSyncMode::On => SwapInterval::VSync,
+            VSyncMode::Adaptive => SwapInterval::LateSwapTearing,
+        };
+
+        if video.gl_set_swap_interval(swap_interval).is_err()
+            && builder.vsync_mode == VSyncMode::Adaptive
+        {
+            // Not every driver supports late-swap-tearing - if it was rejected, fall back to
+            // regular vsync rather than leaving swap timing unset.
+            let _ = video.gl_set_swap_interval(SwapInterval::VSync);
+        }
+
+        sdl.mouse().show_cursor(builder.show_mouse);
+
+        let event_pump = sdl.event_pump().map_err(crate::TetraError::PlatformError)?;
+
+        let (width, height) = window.size();
+
+        let platform = Platform {
+            sdl,
+            _video: video,
+            window,
+            event_pump,
+        };
+
+        Ok((platform, gl_context, width as i32, height as i32))
+    }
+
+    pub(crate) fn gl_swap_window(&self) {
+        self.window.gl_swap_window();
+    }
+}
+
+pub(crate) fn handle_events<S>(ctx: &mut Context, state: &mut S) -> Result
+where
+    S: State,
+{
+    // `event_pump` is borrowed mutably here and `ctx` later, so the events are collected first
+    // and then dispatched, to avoid holding two mutable borrows of `ctx.platform` at once.
+    let events: Vec<SdlEvent> = ctx.platform.event_pump.poll_iter().collect();
+
+    for sdl_event in events {
+        match sdl_event {
+            SdlEvent::Quit { .. } => ctx.running = false,
+
+            SdlEvent::Window {
+                win_event: WindowEvent::Resized(width, height),
+                ..
+            } => {
+                graphics::set_window_size(ctx, width, height);
+                state.event(ctx, Event::Resized { width, height })?;
+            }
+
+            SdlEvent::Window {
+                win_event: WindowEvent::FocusGained,
+                ..
+            } => state.event(ctx, Event::FocusGained)?,
+
+            SdlEvent::Window {
+                win_event: WindowEvent::FocusLost,
+                ..
+            } => state.event(ctx, Event::FocusLost)?,
+
+            SdlEvent::KeyDown {
+                keycode: Some(keycode),
+                repeat: false,
+                ..
+            } => {
+                if let Some(key) = input::map_sdl_key(keycode) {
+                    input::set_key_down(ctx, key);
+                    state.event(ctx, Event::KeyPressed(key))?;
+                }
+            }
+
+            SdlEvent::KeyUp {
+                keycode: Some(keycode),
+                repeat: false,
+                ..
+            } => {
+                if let Some(key) = input::map_sdl_key(keycode) {
+                    input::set_key_up(ctx, key);
+                    state.event(ctx, Event::KeyReleased(key))?;
+                }
+            }
+
+            SdlEvent::TextInput { text, .. } => {
+                state.event(ctx, Event::TextInput(text))?;
+            }
+
+            SdlEvent::DropFile { filename, .. } => {
+                state.event(ctx, Event::FileDropped(filename.into()))?;
+            }
+
+            _ => {}
+        }
+
+        if ctx.quit_on_escape && input::is_key_pressed(ctx, input::Key::Escape) {
+            ctx.running = false;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn run_loop<S, F>(mut ctx: Context, mut state: S, mut run_frame: F)
+where
+    S: State,
+    F: FnMut(&mut Context, &mut S),
+{
+    while ctx.running {
+        run_frame(&mut ctx, &mut state);
+        ctx.platform.gl_swap_window();
+    }
+}
@@ -0,0 +1,113 @@
+//! Functions and types for building games out of a stack of scenes/screens.
+
+use crate::{Context, Event, Result, State};
+
+/// A trait representing a type that contains the game logic for a single scene/screen
+/// (for example, a main menu, a loading screen, or the gameplay itself).
+///
+/// This mirrors the [`State`](crate::State) trait, but its methods return a [`Transition`],
+/// which describes how the [`SceneStack`] should change as a result of the scene running.
+#[allow(unused_variables)]
+pub trait Scene {
+    /// Called when it is time for the scene to update.
+    fn update(&mut self, ctx: &mut Context) -> Result<Transition> {
+        Ok(Transition::None)
+    }
+
+    /// Called when it is time for the scene to be drawn.
+    fn draw(&mut self, ctx: &mut Context, dt: f64) -> Result<Transition> {
+        Ok(Transition::None)
+    }
+
+    /// Called when a window or input event occurs.
+    fn event(&mut self, ctx: &mut Context, event: Event) -> Result<Transition> {
+        Ok(Transition::None)
+    }
+}
+
+/// Describes how a [`SceneStack`] should change after a [`Scene`] has run.
+pub enum Transition {
+    /// Do nothing - carry on running the current scene.
+    None,
+
+    /// Pause the current scene and push a new one on top of it.
+    Push(Box<dyn Scene>),
+
+    /// Pop the current scene off of the stack, resuming whatever scene is underneath it.
+    ///
+    /// If this is the last scene on the stack, the stack will be left empty. An empty stack
+    /// does nothing on update, draw or event, and no further scene can be pushed onto it.
+    Pop,
+
+    /// Replace the current scene with a new one.
+    Switch(Box<dyn Scene>),
+}
+
+/// A stack of [`Scene`]s, which can be used in place of a single top-level [`State`] to allow
+/// a game to be broken up into a set of self-contained screens (e.g. a loading screen, a main
+/// menu, gameplay, and a pause overlay).
+///
+/// `SceneStack` itself implements `State`, so it can be passed straight to
+/// [`ContextBuilder::run`](crate::ContextBuilder::run) - the game loop only ever needs to know
+/// about the scene that is currently on top of the stack.
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Scene>>,
+}
+
+impl SceneStack {
+    /// Creates a new `SceneStack`, with the provided scene as the first one on the stack.
+    pub fn new(initial_scene: Box<dyn Scene>) -> SceneStack {
+        SceneStack {
+            scenes: vec![initial_scene],
+        }
+    }
+
+    fn apply_transition(&mut self, transition: Transition) {
+        match transition {
+            Transition::None => {}
+            Transition::Push(scene) => self.scenes.push(scene),
+            Transition::Pop => {
+                self.scenes.pop();
+            }
+            Transition::Switch(scene) => {
+                self.scenes.pop();
+                self.scenes.push(scene);
+            }
+        }
+    }
+}
+
+impl State for SceneStack {
+    fn update(&mut self, ctx: &mut Context) -> Result {
+        let transition = match self.scenes.last_mut() {
+            Some(active_scene) => active_scene.update(ctx)?,
+            None => Transition::None,
+        };
+
+        self.apply_transition(transition);
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context, dt: f64) -> Result {
+        let transition = match self.scenes.last_mut() {
+            Some(active_scene) => active_scene.draw(ctx, dt)?,
+            None => Transition::None,
+        };
+
+        self.apply_transition(transition);
+
+        Ok(())
+    }
+
+    fn event(&mut self, ctx: &mut Context, event: Event) -> Result {
+        let transition = match self.scenes.last_mut() {
+            Some(active_scene) => active_scene.event(ctx, event)?,
+            None => Transition::None,
+        };
+
+        self.apply_transition(transition);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+//! Functions and types relating to time handling.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::Context;
+
+/// The maximum number of frame times that are kept around for calculating a rolling average FPS.
+const FPS_SAMPLE_SIZE: usize = 200;
+
+/// The number of samples the FPS buffer is pre-filled with, so that the first few real frames
+/// are averaged in alongside plausible data rather than standing alone (which is what let a
+/// single slow startup frame report an absurd FPS until it aged out of the buffer).
+const SEED_SAMPLE_SIZE: usize = 10;
+
+#[derive(Debug)]
+pub(crate) struct TimeContext {
+    tick_rate: Duration,
+
+    last_time: Instant,
+    lag: Duration,
+    overflow: Duration,
+
+    last_frame_time: Instant,
+    frame_times: VecDeque<Duration>,
+    delta_time: Duration,
+}
+
+impl TimeContext {
+    pub(crate) fn new(tick_rate: f64) -> TimeContext {
+        let tick_rate = Duration::from_secs_f64(tick_rate);
+
+        TimeContext {
+            tick_rate,
+
+            last_time: Instant::now(),
+            lag: Duration::from_secs(0),
+            overflow: Duration::from_secs(0),
+
+            last_frame_time: Instant::now(),
+            frame_times: seeded_frame_times(tick_rate),
+            delta_time: tick_rate,
+        }
+    }
+}
+
+fn seeded_frame_times(tick_rate: Duration) -> VecDeque<Duration> {
+    let mut frame_times = VecDeque::with_capacity(FPS_SAMPLE_SIZE);
+    frame_times.extend(std::iter::repeat(tick_rate).take(SEED_SAMPLE_SIZE));
+    frame_times
+}
+
+/// Resets the timers, in case they got desynced from the system time (e.g. after loading
+/// a save, or if the game was suspended for a while).
+pub fn reset(ctx: &mut Context) {
+    ctx.time.last_time = Instant::now();
+    ctx.time.lag = Duration::from_secs(0);
+
+    ctx.time.last_frame_time = Instant::now();
+    ctx.time.frame_times = seeded_frame_times(ctx.time.tick_rate);
+    ctx.time.delta_time = ctx.time.tick_rate;
+}
+
+pub(crate) fn tick(ctx: &mut Context) {
+    let current_time = Instant::now();
+    let elapsed_time = current_time - ctx.time.last_time;
+
+    ctx.time.last_time = current_time;
+    ctx.time.lag += elapsed_time;
+
+    let frame_time = current_time - ctx.time.last_frame_time;
+    ctx.time.last_frame_time = current_time;
+    ctx.time.delta_time = frame_time;
+
+    if ctx.time.frame_times.len() == FPS_SAMPLE_SIZE {
+        ctx.time.frame_times.pop_front();
+    }
+
+    ctx.time.frame_times.push_back(frame_time);
+}
+
+pub(crate) fn is_tick_ready(ctx: &Context) -> bool {
+    ctx.time.lag >= ctx.time.tick_rate
+}
+
+pub(crate) fn consume_tick(ctx: &mut Context) {
+    ctx.time.lag -= ctx.time.tick_rate;
+}
+
+pub(crate) fn consume_all(ctx: &mut Context) {
+    ctx.time.lag = Duration::from_secs(0);
+}
+
+pub(crate) fn get_alpha(ctx: &Context) -> f64 {
+    ctx.time.lag.as_secs_f64() / ctx.time.tick_rate.as_secs_f64()
+}
+
+/// Gets the current frame rate, averaged out over the last few samples.
+///
+/// This is calculated as the number of frames recorded divided by the total time they took to
+/// render, which gives a much less jittery result than taking the reciprocal of the latest
+/// frame's delta time.
+pub fn get_fps(ctx: &Context) -> f64 {
+    let sample_count = ctx.time.frame_times.len();
+
+    if sample_count == 0 {
+        return 0.0;
+    }
+
+    let total_time: Duration = ctx.time.frame_times.iter().sum();
+
+    if total_time.as_secs_f64() == 0.0 {
+        return 0.0;
+    }
+
+    sample_count as f64 / total_time.as_secs_f64()
+}
+
+/// Gets the amount of time that elapsed between the last two frames.
+///
+/// This is the 'raw' duration of the last frame, as opposed to the fixed-timestep `dt` that is
+/// passed to [`State::draw`](crate::State::draw) - it's most useful for profiling, or for driving
+/// things like an on-screen FPS counter.
+pub fn get_delta_time(ctx: &Context) -> Duration {
+    ctx.time.delta_time
+}